@@ -0,0 +1,41 @@
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+
+pub const CHUNK_SIZE: u64 = 65_536;
+
+// Yields a file (or a byte range of one) as a sequence of bounded buffers
+// instead of loading it whole, so serving a large file keeps memory flat.
+pub struct ChunkedReadFile {
+    file: File,
+    remaining: u64,
+}
+
+impl ChunkedReadFile {
+    pub fn new(mut file: File, start: u64, len: u64) -> io::Result<Self> {
+        file.seek(SeekFrom::Start(start))?;
+        Ok(ChunkedReadFile {
+            file,
+            remaining: len,
+        })
+    }
+}
+
+impl Iterator for ChunkedReadFile {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let chunk_len = self.remaining.min(CHUNK_SIZE) as usize;
+        let mut buf = vec![0u8; chunk_len];
+        match self.file.read_exact(&mut buf) {
+            Ok(()) => {
+                self.remaining -= chunk_len as u64;
+                Some(Ok(buf))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// Server-wide behavior that isn't per-request, threaded into `HttpResponse::new`
+// so the operator can opt into dufs-style privacy and web-root conventions.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    hidden: Vec<String>,
+    pub index_mode: IndexMode,
+    pub error_pages: ErrorPages,
+}
+
+// Maps an HTTP status code to a custom HTML template path, overriding the
+// server's built-in `<h1>` markup for that response. A status left unmapped
+// falls back to the built-in body.
+#[derive(Debug, Clone, Default)]
+pub struct ErrorPages {
+    templates: HashMap<u16, PathBuf>,
+}
+
+impl ErrorPages {
+    pub fn new() -> Self {
+        ErrorPages::default()
+    }
+
+    pub fn with_template(mut self, status_code: u16, path: PathBuf) -> Self {
+        self.templates.insert(status_code, path);
+        self
+    }
+
+    pub fn template_for(&self, status_code: u16) -> Option<&PathBuf> {
+        self.templates.get(&status_code)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexMode {
+    // Always generate a directory listing.
+    Off,
+    // Serve `index.html` when a requested directory contains one, falling
+    // back to the directory listing otherwise.
+    Auto,
+}
+
+impl ServerConfig {
+    pub fn new(hidden: Vec<String>, index_mode: IndexMode) -> Self {
+        ServerConfig { hidden, index_mode, error_pages: ErrorPages::new() }
+    }
+
+    pub fn with_error_pages(mut self, error_pages: ErrorPages) -> Self {
+        self.error_pages = error_pages;
+        self
+    }
+
+    // Parse a comma-separated list of names/patterns, e.g. ".git,.env,*.bak".
+    pub fn with_hidden_csv(csv: &str, index_mode: IndexMode) -> Self {
+        let hidden = csv
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+        ServerConfig::new(hidden, index_mode)
+    }
+
+    // Matches exact names (e.g. ".git") and "*"-prefixed suffix patterns
+    // (e.g. "*.bak"); no other wildcard is expanded, so ".*" matches only a
+    // name that is literally ".*".
+    pub fn is_hidden(&self, file_name: &str) -> bool {
+        self.hidden.iter().any(|pattern| match pattern.strip_prefix('*') {
+            Some(suffix) => file_name.ends_with(suffix),
+            None => file_name == pattern,
+        })
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig::new(Vec::new(), IndexMode::Off)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_hidden_matches_exact_names() {
+        let config = ServerConfig::with_hidden_csv(".git,.env", IndexMode::Off);
+        assert!(config.is_hidden(".git"));
+        assert!(config.is_hidden(".env"));
+        assert!(!config.is_hidden("Cargo.toml"));
+    }
+
+    #[test]
+    fn is_hidden_matches_glob_suffix_patterns() {
+        let config = ServerConfig::with_hidden_csv("*.bak", IndexMode::Off);
+        assert!(config.is_hidden("notes.bak"));
+        assert!(!config.is_hidden("notes.txt"));
+    }
+
+    #[test]
+    fn with_hidden_csv_trims_whitespace_and_drops_empty_entries() {
+        let config = ServerConfig::with_hidden_csv(" .git , , *.bak ", IndexMode::Off);
+        assert!(config.is_hidden(".git"));
+        assert!(config.is_hidden("notes.bak"));
+    }
+
+    #[test]
+    fn default_config_hides_nothing() {
+        let config = ServerConfig::default();
+        assert!(!config.is_hidden(".git"));
+    }
+}
@@ -0,0 +1,25 @@
+use pulldown_cmark::{html, Options, Parser};
+
+// Renders Markdown source to a full HTML page, wrapped in the same
+// inline-CSS shell used by the directory listing.
+pub fn render_styled(source: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    let parser = Parser::new_ext(source, options);
+
+    let mut body = String::new();
+    html::push_html(&mut body, parser);
+
+    format!(
+        "<html><head><style>
+        body {{ font-family: Arial, sans-serif; margin: 20px auto; padding: 0; max-width: 900px; }}
+        h1, h2, h3 {{ color: #333; }}
+        pre {{ background: #f5f5f5; padding: 10px; overflow-x: auto; }}
+        code {{ background: #f5f5f5; padding: 2px 4px; }}
+        a {{ text-decoration: none; color: #007bff; }}
+        a:hover {{ text-decoration: underline; color: #0056b3; }}
+        </style></head><body>{}</body></html>",
+        body
+    )
+}
@@ -0,0 +1,5 @@
+pub mod chunked;
+pub mod config;
+pub mod markdown;
+pub mod request;
+pub mod response;
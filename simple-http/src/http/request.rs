@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Method {
+    Get,
+    Post,
+    Uninitialized,
+}
+
+impl From<&str> for Method {
+    fn from(s: &str) -> Method {
+        match s {
+            "GET" => Method::Get,
+            "POST" => Method::Post,
+            _ => Method::Uninitialized,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Version {
+    V1_1,
+    Uninitialized,
+}
+
+impl From<&str> for Version {
+    fn from(s: &str) -> Version {
+        match s {
+            "HTTP/1.1" => Version::V1_1,
+            _ => Version::Uninitialized,
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Version::V1_1 => "HTTP/1.1",
+            Version::Uninitialized => "HTTP/1.1",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+// The request target, split into the path we serve from disk and the raw
+// query string (if any) so callers don't have to re-parse the request line.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Resource {
+    pub path: String,
+    pub query: Option<String>,
+}
+
+// A single-range `Range` request header, in the three forms the HTTP spec
+// allows: an explicit start/end, an open-ended start, or a suffix length
+// counted back from the end of the resource.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Range {
+    FromTo(u64, u64),
+    From(u64),
+    Suffix(u64),
+}
+
+fn parse_range(value: &str) -> Option<Range> {
+    let spec = value.strip_prefix("bytes=")?;
+    // Only a single range is supported; reject list forms like "0-1,2-3".
+    if spec.contains(',') {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+
+    if start.is_empty() {
+        let suffix: u64 = end.parse().ok()?;
+        Some(Range::Suffix(suffix))
+    } else if end.is_empty() {
+        let start: u64 = start.parse().ok()?;
+        Some(Range::From(start))
+    } else {
+        let start: u64 = start.parse().ok()?;
+        let end: u64 = end.parse().ok()?;
+        Some(Range::FromTo(start, end))
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub version: Version,
+    pub resource: Resource,
+    pub headers: HashMap<String, String>,
+    pub msg_body: String,
+    pub range: Option<Range>,
+}
+
+fn process_request_line(s: &str) -> (Method, Resource, Version) {
+    let mut words = s.split_whitespace();
+    let method = words.next().unwrap_or("");
+    let path = words.next().unwrap_or("");
+    let version = words.next().unwrap_or("");
+
+    let (path, query) = match path.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (path.to_string(), None),
+    };
+
+    (
+        method.into(),
+        Resource { path, query },
+        version.into(),
+    )
+}
+
+fn process_header_line(s: &str) -> Option<(String, String)> {
+    let (name, value) = s.split_once(':')?;
+    Some((name.trim().to_lowercase(), value.trim().to_string()))
+}
+
+impl From<String> for HttpRequest {
+    fn from(req: String) -> Self {
+        let mut method = Method::Uninitialized;
+        let mut version = Version::Uninitialized;
+        let mut resource = Resource {
+            path: String::new(),
+            query: None,
+        };
+        let mut headers = HashMap::new();
+        let mut msg_body = String::new();
+
+        for (i, line) in req.lines().enumerate() {
+            if i == 0 {
+                let (m, r, v) = process_request_line(line);
+                method = m;
+                resource = r;
+                version = v;
+            } else if let Some((name, value)) = process_header_line(line) {
+                headers.insert(name, value);
+            } else if !line.is_empty() {
+                msg_body.push_str(line);
+            }
+        }
+
+        let range = headers.get("range").and_then(|v| parse_range(v));
+
+        HttpRequest {
+            method,
+            version,
+            resource,
+            headers,
+            msg_body,
+            range,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_range_from_to() {
+        assert_eq!(parse_range("bytes=0-499"), Some(Range::FromTo(0, 499)));
+    }
+
+    #[test]
+    fn parse_range_from_runs_to_eof() {
+        assert_eq!(parse_range("bytes=500-"), Some(Range::From(500)));
+    }
+
+    #[test]
+    fn parse_range_suffix_counts_back_from_eof() {
+        assert_eq!(parse_range("bytes=-500"), Some(Range::Suffix(500)));
+    }
+
+    #[test]
+    fn parse_range_suffix_of_zero_is_valid() {
+        assert_eq!(parse_range("bytes=-0"), Some(Range::Suffix(0)));
+    }
+
+    #[test]
+    fn parse_range_rejects_missing_bytes_prefix() {
+        assert_eq!(parse_range("0-499"), None);
+    }
+
+    #[test]
+    fn parse_range_rejects_multiple_ranges() {
+        assert_eq!(parse_range("bytes=0-1,2-3"), None);
+    }
+
+    #[test]
+    fn parse_range_rejects_non_numeric_bounds() {
+        assert_eq!(parse_range("bytes=abc-def"), None);
+    }
+
+    #[test]
+    fn parse_range_rejects_missing_dash() {
+        assert_eq!(parse_range("bytes=500"), None);
+    }
+}
@@ -3,8 +3,38 @@ use std::io;
 use walkdir::WalkDir;
 use std::io::ErrorKind;
 
+use super::chunked::ChunkedReadFile;
+use super::config::{IndexMode, ServerConfig};
+use super::markdown;
 use super::request::Version;
 use super::request::HttpRequest;
+use super::request::Range;
+
+// The response body is either held in memory (directory listings, error
+// pages) or streamed off disk in bounded chunks (file content), so that
+// serving a large file never requires buffering it whole.
+pub enum ResponseBody {
+    Bytes(Vec<u8>),
+    Chunked(ChunkedReadFile),
+}
+
+impl From<Vec<u8>> for ResponseBody {
+    fn from(bytes: Vec<u8>) -> Self {
+        ResponseBody::Bytes(bytes)
+    }
+}
+
+impl From<String> for ResponseBody {
+    fn from(s: String) -> Self {
+        ResponseBody::Bytes(s.into_bytes())
+    }
+}
+
+impl From<&str> for ResponseBody {
+    fn from(s: &str) -> Self {
+        ResponseBody::Bytes(s.as_bytes().to_vec())
+    }
+}
 
 #[derive(Debug)]
 pub struct HttpResponse {
@@ -12,17 +42,225 @@ pub struct HttpResponse {
     status: ResponseStatus,
     content_length: usize,
     accept_ranges: AcceptRanges,
-    pub response_body: String,
+    pub response_headers: String,
+    pub response_body: ResponseBody,
     pub current_path: String,
 }
 
+impl std::fmt::Debug for ResponseBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResponseBody::Bytes(b) => f.debug_tuple("Bytes").field(&b.len()).finish(),
+            ResponseBody::Chunked(_) => f.debug_tuple("Chunked").finish(),
+        }
+    }
+}
+
+// A weak validator derived from the file's length and modification time,
+// cheap enough to recompute on every request.
+fn weak_etag(len: u64, modified: std::time::SystemTime) -> String {
+    let mtime = modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    format!("W/\"{:x}-{:x}\"", len, mtime)
+}
+
+// `If-None-Match` takes precedence over `If-Modified-Since` when both are
+// present; modification times are compared at whole-second granularity.
+fn is_not_modified(request: &HttpRequest, etag: &str, modified: std::time::SystemTime) -> bool {
+    if let Some(if_none_match) = request.headers.get("if-none-match") {
+        let if_none_match = if_none_match.trim();
+        return if_none_match == "*" || if_none_match == etag;
+    }
+
+    if let Some(if_modified_since) = request.headers.get("if-modified-since") {
+        if let Ok(since) = httpdate::parse_http_date(if_modified_since) {
+            let modified_secs = modified
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let since_secs = since
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            return modified_secs <= since_secs;
+        }
+    }
+
+    false
+}
+
+// Clamp a requested `Range` against the actual file length, returning an
+// inclusive `(start, end)` byte span. The span may still be unsatisfiable
+// (see `range_is_satisfiable`) — clamping alone can't detect `start >= len`.
+fn resolve_range(range: &Range, len: u64) -> (u64, u64) {
+    match range {
+        Range::FromTo(start, end) => (*start, (*end).min(len.saturating_sub(1))),
+        Range::From(start) => (*start, len.saturating_sub(1)),
+        Range::Suffix(suffix) => (len.saturating_sub((*suffix).min(len)), len.saturating_sub(1)),
+    }
+}
+
+// A resolved range is only servable if the file is non-empty and `start`
+// falls inside it without crossing past `end`.
+fn range_is_satisfiable(start: u64, end: u64, len: u64) -> bool {
+    len > 0 && start <= end && start < len
+}
+
+// Check a raw query string (e.g. "raw=1&foo=bar") for a truthy flag.
+fn query_flag(query: &str, name: &str) -> bool {
+    query.split('&').any(|pair| match pair.split_once('=') {
+        Some((k, v)) => k == name && (v == "1" || v.eq_ignore_ascii_case("true")),
+        None => pair == name,
+    })
+}
+
+// Render a byte count the way a human expects to read it, e.g. "4.2 MiB".
+fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+// The fields of a completed file response, kept together so a second call
+// site (e.g. serving `index.html` for a directory) can reuse the same logic.
+struct FileResponse {
+    status: ResponseStatus,
+    accept_ranges: AcceptRanges,
+    headers: String,
+    body: ResponseBody,
+    content_length: usize,
+}
+
 impl HttpResponse {
-    pub fn new(request: &HttpRequest) -> io::Result<HttpResponse> {
+    // Serves a single file on disk, honoring Range and conditional-GET
+    // request headers. Shared by plain file requests and index.html serving.
+    fn serve_file(path: &std::path::Path, request: &HttpRequest) -> io::Result<FileResponse> {
+        let version = Version::V1_1;
+
+        let is_markdown = matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("md") | Some("markdown")
+        );
+        let raw_requested = request
+            .resource
+            .query
+            .as_deref()
+            .is_some_and(|query| query_flag(query, "raw"));
+
+        if is_markdown && !raw_requested {
+            return Self::serve_markdown(path);
+        }
+
+        let metadata = std::fs::metadata(path)?;
+        let len = metadata.len();
+        let modified = metadata.modified()?;
+        let last_modified = httpdate::fmt_http_date(modified);
+        let etag = weak_etag(len, modified);
+        let accept_ranges = AcceptRanges::Bytes;
+
+        // Infer the MIME type
+        let mime_type = infer::get_from_path(path)?
+            .map_or("application/octet-stream", |t| t.mime_type());
+
+        if is_not_modified(request, &etag, modified) {
+            let status = ResponseStatus::NotModified;
+            let headers = format!(
+                "{} {}\nLast-Modified: {}\nETag: {}\r\n\r\n",
+                version, status, last_modified, etag
+            );
+            return Ok(FileResponse {
+                status,
+                accept_ranges,
+                headers,
+                body: ResponseBody::Bytes(Vec::new()),
+                content_length: 0,
+            });
+        }
+
+        // Resolve the requested byte range, if any, against the file length.
+        let range = request.range.as_ref().map(|range| resolve_range(range, len));
+
+        if let Some((start, end)) = range {
+            if !range_is_satisfiable(start, end, len) {
+                let status = ResponseStatus::RangeNotSatisfiable;
+                let headers = format!(
+                    "{} {}\nContent-Range: bytes */{}\r\n\r\n",
+                    version, status, len
+                );
+                return Ok(FileResponse {
+                    status,
+                    accept_ranges,
+                    headers,
+                    body: ResponseBody::Bytes(Vec::new()),
+                    content_length: 0,
+                });
+            }
+
+            let content_length = (end - start + 1) as usize;
+            let status = ResponseStatus::PartialContent;
+            let headers = format!(
+                "{} {}\n{}\nContent-Type: {}\nContent-Range: bytes {}-{}/{}\nContent-Length: {}\nLast-Modified: {}\nETag: {}\r\n\r\n",
+                version, status, accept_ranges, mime_type, start, end, len, content_length, last_modified, etag
+            );
+            let file = std::fs::File::open(path)?;
+            let body = ResponseBody::Chunked(ChunkedReadFile::new(file, start, content_length as u64)?);
+
+            Ok(FileResponse { status, accept_ranges, headers, body, content_length })
+        } else {
+            let content_length = len as usize;
+            let status = ResponseStatus::OK;
+            let headers = format!(
+                "{} {}\n{}\nContent-Type: {}\nContent-Length: {}\nLast-Modified: {}\nETag: {}\r\n\r\n",
+                version, status, accept_ranges, mime_type, content_length, last_modified, etag
+            );
+            let file = std::fs::File::open(path)?;
+            let body = ResponseBody::Chunked(ChunkedReadFile::new(file, 0, len)?);
+
+            Ok(FileResponse { status, accept_ranges, headers, body, content_length })
+        }
+    }
+
+    // Renders a `.md`/`.markdown` file to an HTML page instead of serving it
+    // raw; pass `?raw=1` on the request to skip this and download the source.
+    fn serve_markdown(path: &std::path::Path) -> io::Result<FileResponse> {
+        let version = Version::V1_1;
+        let source = std::fs::read_to_string(path)?;
+        let body_html = markdown::render_styled(&source);
+
+        let status = ResponseStatus::OK;
+        let content_length = body_html.len();
+        let headers = format!(
+            "{} {}\nContent-Type: text/html\nContent-Length: {}\r\n\r\n",
+            version, status, content_length
+        );
+
+        Ok(FileResponse {
+            status,
+            accept_ranges: AcceptRanges::None,
+            headers,
+            body: body_html.into(),
+            content_length,
+        })
+    }
+
+    pub fn new(request: &HttpRequest, config: &ServerConfig) -> io::Result<HttpResponse> {
         let version: Version = Version::V1_1;
         let mut status: ResponseStatus = ResponseStatus::NotFound;
         let mut content_length: usize = 0;
         let mut accept_ranges: AcceptRanges = AcceptRanges::None;
-        let mut response_body = String::new();
+        let mut response_headers = String::new();
+        let mut response_body: ResponseBody = ResponseBody::Bytes(Vec::new());
 
         let server_root_path = std::env::current_dir()?; // Get current working directory (root of the server)
         let resource = if request.resource.path.is_empty() || request.resource.path == "/" {
@@ -30,7 +268,7 @@ impl HttpResponse {
         } else {
             request.resource.path.clone() // Get the requested path
         };
-        let new_path = server_root_path.join(&resource);        
+        let new_path = server_root_path.join(&resource);
 
         // Log the requested path
         println!("Requested path: {:?}", new_path);
@@ -40,101 +278,155 @@ impl HttpResponse {
 
         // Check if path is within the server root
         if rootcwd_len > resource_len {
-            status = ResponseStatus::NotFound;
-            response_body = "<html><body><h1>403 Forbidden</h1></body></html>".to_string();
-            content_length = response_body.len();
-            println!("403 Response: {}", response_body);
-        } 
+            status = ResponseStatus::Forbidden;
+            let body = error_body(config, status);
+            content_length = body.len();
+            response_headers = format!(
+                "{} {}\nContent-Type: text/html\nContent-Length: {}\r\n\r\n",
+                version, status, content_length
+            );
+            response_body = body.into();
+            println!("403 Response: {} bytes", content_length);
+        }
         // Check if the requested path exists
         else if new_path.exists() {
             // If it's a file, serve the file content
             if new_path.is_file() {
                 println!("Serving file: {:?}", new_path);
-                
-                let content = std::fs::read(&new_path)?;  // Read the file content
-                content_length = content.len();
-                status = ResponseStatus::OK;
-                accept_ranges = AcceptRanges::Bytes;
-
-                // Infer the MIME type
-                let mime_type = infer::get_from_path(&new_path)?
-                    .map_or("application/octet-stream", |t| t.mime_type());
-
-                response_body = format!(
-                    "{} {}\n{}\nContent-Type: {}\nContent-Length: {}\r\n\r\n",
-                    version, status, accept_ranges, mime_type, content_length
-                );
-                response_body.push_str(&String::from_utf8_lossy(&content));  // Append the file content
-                
-                println!("Response Body for File: {}", response_body);
-            } 
+
+                let file_response = Self::serve_file(&new_path, request)?;
+                status = file_response.status;
+                accept_ranges = file_response.accept_ranges;
+                response_headers = file_response.headers;
+                response_body = file_response.body;
+                content_length = file_response.content_length;
+
+                println!("Served file, {} header bytes, {} body bytes", response_headers.len(), content_length);
+            }
             // If it's a directory, generate a directory listing
             else if new_path.is_dir() {
-                println!("Serving directory: {:?}", new_path);
+                // Redirect to the trailing-slash form first, so relative links in the
+                // listing (and the "go back up" link) resolve against the right base.
+                if resource != "." && !request.resource.path.ends_with('/') {
+                    println!("Redirecting to trailing slash: {:?}", new_path);
 
-                let mut dir_list = String::new(); // Prepare directory list
+                    status = ResponseStatus::MovedPermanently;
+                    response_headers = format!(
+                        "{} {}\nLocation: /{}/\r\n\r\n",
+                        version, status, resource.trim_start_matches('/')
+                    );
+                } else if config.index_mode == IndexMode::Auto && new_path.join("index.html").is_file() {
+                    println!("Serving index.html for directory: {:?}", new_path);
 
-                // Add "Go Back" button unless we're at the root directory
-                if resource != "." {
-                    let parent_path = std::path::Path::new(&resource).parent().unwrap_or_else(|| std::path::Path::new("/")).display().to_string();
-                    dir_list.push_str(&format!(
-                        "<li><a href=\"/{}\">Go back up a directory</a></li>", 
-                        parent_path
-                    ));
-                }
+                    let file_response = Self::serve_file(&new_path.join("index.html"), request)?;
+                    status = file_response.status;
+                    accept_ranges = file_response.accept_ranges;
+                    response_headers = file_response.headers;
+                    response_body = file_response.body;
+                    content_length = file_response.content_length;
+                } else {
+                    println!("Serving directory: {:?}", new_path);
 
-                // Iterate through the directory and collect file/folder names
-                for entry in WalkDir::new(&new_path).min_depth(1).max_depth(1) {
-                    let entry = entry?;
-                    let file_name = entry.file_name().to_string_lossy();
-
-                    // Generate the file path relative to the server root for correct linking
-                    let file_path = match entry.path().strip_prefix(&server_root_path) {
-                        Ok(path) => path.display().to_string(),
-                        Err(_) => return Err(std::io::Error::new(ErrorKind::Other, "Failed to strip prefix")),
-                    };
-
-                    // Create clickable links for files/folders
-                    dir_list.push_str(&format!(
-                        "<li><a href=\"/{}\">{}</a></li>", 
-                        file_path, file_name
-                    ));
-                }
+                    let mut entries: Vec<_> = WalkDir::new(&new_path)
+                        .min_depth(1)
+                        .max_depth(1)
+                        .into_iter()
+                        .collect::<walkdir::Result<Vec<_>>>()?;
 
-                // Build the HTML body with the directory listing and include inline CSS for styling
-                response_body = format!(
-                    "<html><head><style>
-                    body {{ font-family: Arial, sans-serif; margin: 20px; padding: 0; }}
-                    h1 {{ color: #333; }}
-                    ul {{ list-style-type: none; padding: 0; }}
-                    li {{ margin-bottom: 10px; }}
-                    a {{ text-decoration: none; color: #007bff; font-size: 16px; }}
-                    a:hover {{ text-decoration: underline; color: #0056b3; }}
-                    </style></head><body>
-                    <h1>Directory Listing</h1>
-                    <ul>{}</ul></body></html>", 
-                    dir_list
-                );
+                    // Skip names the operator has configured as hidden (dotfiles, `.git`, ...).
+                    entries.retain(|entry| !config.is_hidden(&entry.file_name().to_string_lossy()));
 
-                // Calculate content length after the response body is fully generated
-                content_length = response_body.len();
+                    // Directories before files, alphabetically within each group.
+                    entries.sort_by(|a, b| {
+                        let a_is_dir = a.file_type().is_dir();
+                        let b_is_dir = b.file_type().is_dir();
+                        b_is_dir.cmp(&a_is_dir).then_with(|| a.file_name().cmp(b.file_name()))
+                    });
 
-                // Include headers in the response body
-                response_body = format!(
-                    "{} {}\n{}\nContent-Type: text/html\nContent-Length: {}\r\n\r\n{}", 
-                    version, status, accept_ranges, content_length, response_body
-                );
+                    let mut dir_list = String::new(); // Prepare directory list
+
+                    // Add "Go Back" button unless we're at the root directory
+                    if resource != "." {
+                        // Strip the leading slash before re-deriving the parent, the same way
+                        // entry links below are built off the server-root-relative path, so
+                        // this doesn't double up into a protocol-relative "//" href.
+                        let parent_path = std::path::Path::new(resource.trim_start_matches('/'))
+                            .parent()
+                            .unwrap_or_else(|| std::path::Path::new(""))
+                            .display()
+                            .to_string();
+                        dir_list.push_str(&format!(
+                            "<tr><td colspan=\"2\"><a href=\"/{}\">.. Go back up a directory</a></td></tr>",
+                            parent_path
+                        ));
+                    }
+
+                    for entry in entries {
+                        let file_name = entry.file_name().to_string_lossy();
+                        let is_dir = entry.file_type().is_dir();
+
+                        // Generate the file path relative to the server root for correct linking
+                        let file_path = match entry.path().strip_prefix(&server_root_path) {
+                            Ok(path) => path.display().to_string(),
+                            Err(_) => return Err(std::io::Error::new(ErrorKind::Other, "Failed to strip prefix")),
+                        };
 
-                status = ResponseStatus::OK;
+                        let (icon, size_label) = if is_dir {
+                            ("\u{1F4C1}", "-".to_string())
+                        } else {
+                            ("\u{1F4C4}", human_size(entry.metadata()?.len()))
+                        };
 
-                println!("Response Body for Directory: {}", response_body);
+                        dir_list.push_str(&format!(
+                            "<tr><td>{} <a href=\"/{}{}\">{}</a></td><td>{}</td></tr>",
+                            icon,
+                            file_path,
+                            if is_dir { "/" } else { "" },
+                            file_name,
+                            size_label
+                        ));
+                    }
+
+                    // Build the HTML body with the directory listing and include inline CSS for styling
+                    let body_html = format!(
+                        "<html><head><style>
+                        body {{ font-family: Arial, sans-serif; margin: 20px; padding: 0; }}
+                        h1 {{ color: #333; }}
+                        table {{ border-collapse: collapse; width: 100%; }}
+                        td {{ padding: 6px 10px; text-align: left; }}
+                        td:last-child {{ text-align: right; color: #666; }}
+                        a {{ text-decoration: none; color: #007bff; font-size: 16px; }}
+                        a:hover {{ text-decoration: underline; color: #0056b3; }}
+                        </style></head><body>
+                        <h1>Directory Listing</h1>
+                        <table>{}</table></body></html>",
+                        dir_list
+                    );
+
+                    status = ResponseStatus::OK;
+                    content_length = body_html.len();
+
+                    response_headers = format!(
+                        "{} {}\n{}\nContent-Type: text/html\nContent-Length: {}\r\n\r\n",
+                        version, status, accept_ranges, content_length
+                    );
+                    response_body = body_html.into();
+
+                    println!("Served directory listing, {} header bytes + {} body bytes", response_headers.len(), content_length);
+                }
             }
-        } 
+        }
         // Handle case when the resource doesn't exist (404 Not Found)
         else {
-            response_body = "<html><body><h1>404 Not Found</h1></body></html>".to_string();
-            content_length = response_body.len();
-            println!("404 Response: {}", response_body);
+            status = ResponseStatus::NotFound;
+            let body = error_body(config, status);
+            content_length = body.len();
+            response_headers = format!(
+                "{} {}\nContent-Type: text/html\nContent-Length: {}\r\n\r\n",
+                version, status, content_length
+            );
+            response_body = body.into();
+            println!("404 Response: {} bytes", content_length);
         }
 
         // Return the constructed HTTP response
@@ -143,6 +435,7 @@ impl HttpResponse {
             status,
             content_length,
             accept_ranges,
+            response_headers,
             response_body,
             current_path: request.resource.path.clone(),
         })
@@ -150,22 +443,60 @@ impl HttpResponse {
 }
 
 // Enum to represent HTTP response status codes
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum ResponseStatus {
     OK = 200,
+    PartialContent = 206,
+    MovedPermanently = 301,
+    NotModified = 304,
+    Forbidden = 403,
     NotFound = 404,
+    RangeNotSatisfiable = 416,
+}
+
+impl ResponseStatus {
+    fn code(self) -> u16 {
+        self as u16
+    }
 }
 
 impl Display for ResponseStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let msg = match self {
             ResponseStatus::OK => "200 OK",
+            ResponseStatus::PartialContent => "206 PARTIAL CONTENT",
+            ResponseStatus::MovedPermanently => "301 MOVED PERMANENTLY",
+            ResponseStatus::NotModified => "304 NOT MODIFIED",
+            ResponseStatus::Forbidden => "403 FORBIDDEN",
             ResponseStatus::NotFound => "404 NOT FOUND",
+            ResponseStatus::RangeNotSatisfiable => "416 RANGE NOT SATISFIABLE",
         };
         write!(f, "{}", msg)
     }
 }
 
+// Built-in markup used when the operator hasn't configured a custom
+// template for a given status via `ErrorPages`.
+fn default_error_body(status: ResponseStatus) -> &'static str {
+    match status {
+        ResponseStatus::Forbidden => "<html><body><h1>403 Forbidden</h1></body></html>",
+        ResponseStatus::NotFound => "<html><body><h1>404 Not Found</h1></body></html>",
+        _ => "<html><body><h1>Error</h1></body></html>",
+    }
+}
+
+// The default resource handler: serve the operator's custom HTML template
+// for this status if one is configured and readable, otherwise fall back to
+// the built-in body — a misconfigured template should degrade the same way
+// an absent one does, not take the whole response down with it.
+fn error_body(config: &ServerConfig, status: ResponseStatus) -> Vec<u8> {
+    config
+        .error_pages
+        .template_for(status.code())
+        .and_then(|path| std::fs::read(path).ok())
+        .unwrap_or_else(|| default_error_body(status).as_bytes().to_vec())
+}
+
 // Enum to represent Accept-Ranges header
 #[derive(Debug)]
 enum AcceptRanges {
@@ -182,3 +513,182 @@ impl Display for AcceptRanges {
         write!(f, "{}", msg)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn request_with_headers(headers: &[(&str, &str)]) -> HttpRequest {
+        let mut map = HashMap::new();
+        for (name, value) in headers {
+            map.insert(name.to_string(), value.to_string());
+        }
+        HttpRequest {
+            method: super::super::request::Method::Get,
+            version: Version::V1_1,
+            resource: super::super::request::Resource { path: "/".to_string(), query: None },
+            headers: map,
+            msg_body: String::new(),
+            range: None,
+        }
+    }
+
+    #[test]
+    fn weak_etag_encodes_len_and_mtime_as_hex() {
+        let modified = UNIX_EPOCH + Duration::from_secs(0x2a);
+        assert_eq!(weak_etag(0x10, modified), "W/\"10-2a\"");
+    }
+
+    #[test]
+    fn is_not_modified_true_on_matching_etag() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1000);
+        let etag = weak_etag(42, modified);
+        let request = request_with_headers(&[("if-none-match", &etag)]);
+        assert!(is_not_modified(&request, &etag, modified));
+    }
+
+    #[test]
+    fn is_not_modified_true_on_wildcard_if_none_match() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1000);
+        let etag = weak_etag(42, modified);
+        let request = request_with_headers(&[("if-none-match", "*")]);
+        assert!(is_not_modified(&request, &etag, modified));
+    }
+
+    #[test]
+    fn is_not_modified_false_on_etag_mismatch() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1000);
+        let etag = weak_etag(42, modified);
+        let request = request_with_headers(&[("if-none-match", "W/\"stale\"")]);
+        assert!(!is_not_modified(&request, &etag, modified));
+    }
+
+    #[test]
+    fn is_not_modified_ignores_if_modified_since_when_if_none_match_present() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1000);
+        let etag = weak_etag(42, modified);
+        let request = request_with_headers(&[
+            ("if-none-match", "W/\"stale\""),
+            ("if-modified-since", &httpdate::fmt_http_date(modified)),
+        ]);
+        assert!(!is_not_modified(&request, &etag, modified));
+    }
+
+    #[test]
+    fn is_not_modified_true_when_if_modified_since_is_not_older() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1000);
+        let etag = weak_etag(42, modified);
+        let request = request_with_headers(&[("if-modified-since", &httpdate::fmt_http_date(modified))]);
+        assert!(is_not_modified(&request, &etag, modified));
+    }
+
+    #[test]
+    fn is_not_modified_false_when_if_modified_since_is_older() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1000);
+        let etag = weak_etag(42, modified);
+        let since = httpdate::fmt_http_date(UNIX_EPOCH + Duration::from_secs(900));
+        let request = request_with_headers(&[("if-modified-since", &since)]);
+        assert!(!is_not_modified(&request, &etag, modified));
+    }
+
+    #[test]
+    fn is_not_modified_false_without_conditional_headers() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1000);
+        let etag = weak_etag(42, modified);
+        let request = request_with_headers(&[]);
+        assert!(!is_not_modified(&request, &etag, modified));
+    }
+
+    #[test]
+    fn human_size_under_a_kibibyte_has_no_decimal() {
+        assert_eq!(human_size(512), "512 B");
+    }
+
+    #[test]
+    fn human_size_scales_to_kibibytes() {
+        assert_eq!(human_size(1536), "1.5 KiB");
+    }
+
+    #[test]
+    fn human_size_scales_to_mebibytes() {
+        assert_eq!(human_size(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn human_size_caps_out_at_tebibytes() {
+        assert_eq!(human_size(u64::MAX), format!("{:.1} TiB", u64::MAX as f64 / 1024f64.powi(4)));
+    }
+
+    #[test]
+    fn resolve_range_from_to_clamps_end_to_last_byte() {
+        assert_eq!(resolve_range(&Range::FromTo(0, 999), 100), (0, 99));
+        assert_eq!(resolve_range(&Range::FromTo(10, 20), 100), (10, 20));
+    }
+
+    #[test]
+    fn resolve_range_from_runs_to_end_of_file() {
+        assert_eq!(resolve_range(&Range::From(10), 100), (10, 99));
+    }
+
+    #[test]
+    fn resolve_range_suffix_counts_back_from_end() {
+        assert_eq!(resolve_range(&Range::Suffix(10), 100), (90, 99));
+        // A suffix larger than the file just means "the whole file".
+        assert_eq!(resolve_range(&Range::Suffix(1000), 100), (0, 99));
+    }
+
+    #[test]
+    fn range_is_satisfiable_rejects_empty_file() {
+        assert!(!range_is_satisfiable(0, 0, 0));
+    }
+
+    #[test]
+    fn range_is_satisfiable_rejects_start_past_end_of_file() {
+        assert!(!range_is_satisfiable(100, 100, 100));
+    }
+
+    #[test]
+    fn range_is_satisfiable_rejects_start_after_end() {
+        assert!(!range_is_satisfiable(50, 10, 100));
+    }
+
+    #[test]
+    fn range_is_satisfiable_accepts_in_bounds_span() {
+        assert!(range_is_satisfiable(0, 99, 100));
+        assert!(range_is_satisfiable(99, 99, 100));
+    }
+
+    #[test]
+    fn error_body_falls_back_to_default_when_no_template_configured() {
+        let config = ServerConfig::default();
+        let body = error_body(&config, ResponseStatus::NotFound);
+        assert_eq!(body, default_error_body(ResponseStatus::NotFound).as_bytes());
+    }
+
+    #[test]
+    fn error_body_falls_back_to_default_when_template_path_is_unreadable() {
+        let config = ServerConfig::default().with_error_pages(
+            super::super::config::ErrorPages::new()
+                .with_template(404, std::path::PathBuf::from("/does/not/exist.html")),
+        );
+        let body = error_body(&config, ResponseStatus::NotFound);
+        assert_eq!(body, default_error_body(ResponseStatus::NotFound).as_bytes());
+    }
+
+    #[test]
+    fn error_body_reads_configured_template_when_present() {
+        let mut template = std::env::temp_dir();
+        template.push(format!("simple-http-test-error-{}.html", std::process::id()));
+        std::fs::write(&template, "<p>custom 403</p>").unwrap();
+
+        let config = ServerConfig::default().with_error_pages(
+            super::super::config::ErrorPages::new().with_template(403, template.clone()),
+        );
+        let body = error_body(&config, ResponseStatus::Forbidden);
+        assert_eq!(body, b"<p>custom 403</p>");
+
+        std::fs::remove_file(&template).unwrap();
+    }
+}